@@ -0,0 +1,6 @@
+fn main() {
+    // Registers `--cfg loom` with rustc so the `sync` module's `#[cfg(loom)]`
+    // gate doesn't trip `unexpected_cfgs` under `-D warnings` when the crate
+    // is built without it.
+    println!("cargo:rustc-check-cfg=cfg(loom)");
+}
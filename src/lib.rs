@@ -31,9 +31,11 @@
 //! This crate exports a single type `LeftRightRwLock<T>` with 3 operations:
 //! `new`, `read` and `write`. `read` and `write` operations are thread-safe,
 //! they take lambdas as arguments which observe or mutate the data structure.
+//! `read` claims a reader shard for the calling thread automatically; use
+//! `read_with_id` if you need to assign reader identities yourself.
 //!
 //! # Sample usage
-//! 
+//!
 //! ```rust
 //! extern crate left_right_rw_lock;
 //! use left_right_rw_lock::LeftRightRwLock;
@@ -44,40 +46,181 @@
 //!    let data = Arc::new(LeftRightRwLock::new(|| Vec::<i32>::new(), 10));
 //!    let mut threads = Vec::new();
 //!
-//!    for i in 0..5000 {
+//!    for _ in 0..5000 {
 //!        let data = data.clone();
 //!        threads.push(thread::spawn(move || {
 //!            data.write(|vec| vec.push(1));
-//!            assert!(data.read(i, |vec| vec.iter().fold(0, |acc, &item| acc + item)) > 0);
+//!            assert!(data.read(|vec| vec.iter().fold(0, |acc, &item| acc + item)) > 0);
 //!        }));
 //!    }
-//!    
+//!
 //!    for t in threads {
 //!        t.join().unwrap()
 //!    }
-//!    
-//!    assert_eq!(data.read(1, |vec| vec.iter().fold(0, |acc, &item| acc + item)), 5000);
+//!
+//!    assert_eq!(data.read(|vec| vec.iter().fold(0, |acc, &item| acc + item)), 5000);
 //! }
 //! ```
 
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Mutex;
-use std::cell::UnsafeCell;
-use std::marker::Sync;
+use std::marker::{PhantomData, Sync};
+use std::ops::{Deref, DerefMut};
+
+// The correctness of this crate hinges entirely on the interleaving of
+// `instance_index`/`indicator_index` toggles against reader arrive/depart,
+// which is exactly what Loom's exhaustive scheduler is built to check. This
+// module routes every atomic, mutex and unsafe cell through Loom's
+// equivalents under `--cfg loom` (see tests/loom.rs) and through `std`
+// otherwise, so the same production code gets model-checked.
+#[cfg(not(loom))]
+mod sync {
+    pub(crate) use std::sync::atomic::{fence, AtomicUsize, Ordering};
+    pub(crate) use std::sync::{Mutex, MutexGuard};
+    pub(crate) use std::thread::yield_now;
+
+    pub(crate) struct UnsafeCell<T>(std::cell::UnsafeCell<T>);
+
+    impl<T> UnsafeCell<T> {
+        pub(crate) fn new(data: T) -> UnsafeCell<T> {
+            UnsafeCell(std::cell::UnsafeCell::new(data))
+        }
+
+        pub(crate) fn with<R>(&self, f: impl FnOnce(*const T) -> R) -> R {
+            f(self.0.get())
+        }
+
+        pub(crate) fn with_mut<R>(&self, f: impl FnOnce(*mut T) -> R) -> R {
+            f(self.0.get())
+        }
+    }
+}
+
+#[cfg(loom)]
+mod sync {
+    pub(crate) use loom::sync::atomic::{fence, AtomicUsize, Ordering};
+    pub(crate) use loom::sync::{Mutex, MutexGuard};
+    pub(crate) use loom::cell::UnsafeCell;
+    pub(crate) use loom::thread::yield_now;
+}
+
+use sync::{fence, yield_now, AtomicUsize, Mutex, MutexGuard, Ordering, UnsafeCell};
+
+// Padded to a common cache line size (64 bytes covers x86-64 and most
+// ARM64 parts) so that neighbouring shards or replicas, touched by
+// different threads, never false-share a line. Used for both the
+// indicator shards below and the two instance replicas.
+#[repr(align(64))]
+struct CachePadded<T>(T);
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
 
-// TODO: spread instances onto separate cache lines
-// TODO: spread indicators onto separate cache lines
-pub struct LeftRightRwLock<T> {
-    instances       : UnsafeCell<[T; 2]>,
+impl<T> DerefMut for CachePadded<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+// Hands out a small, dense, per-thread reader identity the first time a
+// thread calls `read`, mirroring what the `thread-id` crate / crossbeam's
+// `ShardedLock` do. Shards are claimed lazily: the thread_local initializer
+// only runs on first access, so a thread that never reads never claims one.
+//
+// This id allocator deliberately stays on `std`'s atomics rather than the
+// `sync` abstraction above: it plays no part in the instance/indicator
+// toggling protocol Loom is model-checking, and Loom's atomics aren't
+// `const fn`-constructible, so they can't sit in a `static` like this one.
+static NEXT_READER_ID: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+thread_local! {
+    static READER_ID: usize = NEXT_READER_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+}
+
+fn current_reader_id() -> usize {
+    READER_ID.with(|id| *id)
+}
+
+/// Relax strategy used by `write()` while it drains an indicator, modeled
+/// on crossbeam-utils' `Backoff` and spin's `RelaxStrategy`. Implementors
+/// typically escalate on repeated calls and are constructed fresh (via
+/// `Default`) at the start of each drain loop.
+pub trait RelaxStrategy: Default {
+    /// Relax once. Call in a loop; strategies may escalate on repeated calls.
+    fn relax(&mut self);
+}
+
+#[cfg(not(loom))]
+const SPIN_LIMIT: u32 = 6;
+#[cfg(not(loom))]
+const YIELD_LIMIT: u32 = 10;
+
+/// Default [`RelaxStrategy`]: spins a handful of times, escalates to
+/// `yield_now`, and finally to a short parked sleep. Good default for a
+/// writer that expects readers to depart quickly but shouldn't spin a
+/// whole core indefinitely if one doesn't.
+#[derive(Default)]
+pub struct ExponentialBackoff {
+    step: u32,
+}
+
+impl RelaxStrategy for ExponentialBackoff {
+    fn relax(&mut self) {
+        // Loom doesn't treat a spin_loop hint or a real sleep as a
+        // scheduling point, so under the model every relax() just yields
+        // to the scheduler; otherwise a thread that spins without ever
+        // yielding looks to Loom like it's failing to make progress, and
+        // the model check blows up exploring that non-progress.
+        #[cfg(loom)]
+        yield_now();
+
+        #[cfg(not(loom))]
+        if self.step <= SPIN_LIMIT {
+            for _ in 0..1u32 << self.step {
+                std::hint::spin_loop();
+            }
+        } else if self.step <= YIELD_LIMIT {
+            yield_now();
+        } else {
+            std::thread::sleep(std::time::Duration::from_micros(50));
+        }
+
+        self.step += 1;
+    }
+}
+
+/// [`RelaxStrategy`] that only ever spins. Suited to latency-sensitive
+/// writers on otherwise-idle cores, where readers are expected to depart
+/// almost immediately and yielding/parking would only add latency.
+#[derive(Default)]
+pub struct SpinRelax;
+
+impl RelaxStrategy for SpinRelax {
+    fn relax(&mut self) {
+        // See ExponentialBackoff::relax(): Loom needs an actual yield to
+        // make progress, a spin_loop hint doesn't count as one.
+        #[cfg(loom)]
+        yield_now();
+        #[cfg(not(loom))]
+        std::hint::spin_loop();
+    }
+}
+
+pub struct LeftRightRwLock<T, B = ExponentialBackoff> {
+    instances       : [CachePadded<UnsafeCell<T>>; 2],
     instance_index  : AtomicUsize,
-    
-    indicators      : Vec<[AtomicUsize; 2]>,
+
+    indicators      : Vec<CachePadded<[AtomicUsize; 2]>>,
     indicator_index : AtomicUsize,
 
     write_mutex     : Mutex<bool>,
+    backoff         : PhantomData<B>,
 }
 
-unsafe impl<T> Sync for LeftRightRwLock<T> {}
+unsafe impl<T, B> Sync for LeftRightRwLock<T, B> {}
 
 /**
  * The following concurrency characteristics are desirable:
@@ -85,24 +228,51 @@ unsafe impl<T> Sync for LeftRightRwLock<T> {}
  *  read()  - Wait-Free (at least on x86)
  *  write() - Blocking
  */
-impl<T> LeftRightRwLock<T> {
+impl<T, B: RelaxStrategy> LeftRightRwLock<T, B> {
     fn indicator_arrive(&self, id: usize, index: usize) {
         let modulo_id = id % self.indicators.len();
-        self.indicators[modulo_id][index].fetch_add(1, Ordering::SeqCst);
+        self.indicators[modulo_id][index].fetch_add(1, Ordering::AcqRel);
     }
 
     fn indicator_depart(&self, id: usize, index: usize) {
         let modulo_id = id % self.indicators.len();
-        self.indicators[modulo_id][index].fetch_sub(1, Ordering::SeqCst);
+        self.indicators[modulo_id][index].fetch_sub(1, Ordering::Release);
     }
 
-    pub fn read<Fr, R>(&self, reader_id: usize, reader : Fr) -> R
+    /// Like [`read_with_id`](LeftRightRwLock::read_with_id), but claims the
+    /// calling thread's shard automatically instead of taking an explicit
+    /// `reader_id`. This is the method to reach for unless you're on no_std
+    /// or otherwise can't rely on thread-locals.
+    pub fn read<Fr, R>(&self, reader : Fr) -> R
         where Fr : Fn(&T) -> R
     {
-        let local_inidicator_index = self.indicator_index.load(Ordering::SeqCst);
+        self.read_with_id(current_reader_id(), reader)
+    }
+
+    pub fn read_with_id<Fr, R>(&self, reader_id: usize, reader : Fr) -> R
+        where Fr : Fn(&T) -> R
+    {
+        // Arriving at the indicator before reading `instance_index` (and the
+        // writer issuing a SeqCst fence right after flipping it, see write())
+        // is what rules out a reader observing a half-updated instance: the
+        // arrive/fence pair plays the role the paper's SeqCst loads used to.
+        let local_inidicator_index = self.indicator_index.load(Ordering::Acquire);
 
         self.indicator_arrive(reader_id, local_inidicator_index);
-        let result = unsafe { reader(& (*self.instances.get())[self.instance_index.load(Ordering::SeqCst)]) };
+
+        // This is the store-buffer litmus test: an Acquire/AcqRel pairing
+        // alone does not forbid the writer's SeqCst store + fence from
+        // being reordered past this arrive on weakly-ordered hardware,
+        // which would let this reader observe the *old* instance_index
+        // while the writer's indicator scan misses this arrival. A SeqCst
+        // fence here joins the single total order established by the
+        // writer's own SeqCst store + fence (see write()), which is what
+        // actually closes that window; it happens to hold on x86 without
+        // this because `fetch_add` already compiles to a full barrier
+        // there, but not on ARM/Power.
+        fence(Ordering::SeqCst);
+        let index = self.instance_index.load(Ordering::SeqCst);
+        let result = self.instances[index].with(|instance| unsafe { reader(&*instance) });
         self.indicator_depart(reader_id, local_inidicator_index);
 
         result
@@ -117,42 +287,428 @@ impl<T> LeftRightRwLock<T> {
     {
         let _guard = self.write_mutex.lock().unwrap();
         let local_instance_index = self.instance_index.load(Ordering::SeqCst);
-        
-        unsafe { writer(&mut (*self.instances.get())[1-local_instance_index]); }
-        
+
+        self.instances[1 - local_instance_index].with_mut(|instance| unsafe {
+            writer(&mut *instance);
+        });
+
         self.instance_index.store(1-local_instance_index, Ordering::SeqCst);
-        
+
+        // Pairs with the SeqCst fence in read_with_id()/read_guard_with_id():
+        // a reader whose fence follows this one sees the new instance_index,
+        // and one whose fence precedes it has its arrival visible to the
+        // indicator scans below, so they can't miss a reader of the old
+        // instance.
+        fence(Ordering::SeqCst);
+
         let previous_indicator_index = self.indicator_index.load(Ordering::SeqCst);
         let next_indicator_index = 1-previous_indicator_index;
+        let mut backoff = B::default();
         while self.indicator_is_set(next_indicator_index) {
-            std::thread::yield_now();
+            backoff.relax();
         }
 
         self.indicator_index.store(next_indicator_index, Ordering::SeqCst);
 
+        let mut backoff = B::default();
         while self.indicator_is_set(previous_indicator_index) {
-            std::thread::yield_now();
+            backoff.relax();
+        }
+
+        self.instances[local_instance_index].with_mut(|instance| unsafe { writer(&mut *instance) })
+    }
+
+    /// Like [`read`](LeftRightRwLock::read), but returns an RAII guard
+    /// instead of taking a closure, for callers that want to hold the
+    /// borrow across several statements or return early.
+    pub fn read_guard(&self) -> ReadGuard<'_, T, B> {
+        self.read_guard_with_id(current_reader_id())
+    }
+
+    pub fn read_guard_with_id(&self, reader_id: usize) -> ReadGuard<'_, T, B> {
+        let local_indicator_index = self.indicator_index.load(Ordering::Acquire);
+        self.indicator_arrive(reader_id, local_indicator_index);
+
+        // See read_with_id() for why this fence has to be SeqCst: an
+        // Acquire/AcqRel pairing alone doesn't forbid the writer's SeqCst
+        // store + fence from being reordered past this arrive on weak
+        // memory, which would let this guard capture the old
+        // instance_index while the writer's scan misses the arrival.
+        fence(Ordering::SeqCst);
+        let local_instance_index = self.instance_index.load(Ordering::SeqCst);
+
+        ReadGuard {
+            lock            : self,
+            indicator_index : local_indicator_index,
+            reader_id,
+            instance_index  : local_instance_index,
         }
+    }
+
+    /// Like [`write`](LeftRightRwLock::write), but returns an RAII
+    /// transaction guard instead of taking a closure, for callers that
+    /// want to mutate the standby replica across several statements
+    /// rather than build a single reusable `Fn`. Requires `T: Clone`: the
+    /// guard can't capture what the caller did through `DerefMut` as a
+    /// replayable operation, so on `Drop` it clones the now-current
+    /// replica over the other one instead of replaying a closure.
+    pub fn write_guard(&self) -> WriteGuard<'_, T, B>
+        where T : Clone
+    {
+        let mutex_guard = self.write_mutex.lock().unwrap();
+        let local_instance_index = self.instance_index.load(Ordering::SeqCst);
 
-        unsafe { writer(&mut (*self.instances.get())[local_instance_index]) }
+        WriteGuard {
+            lock                  : self,
+            _mutex_guard          : mutex_guard,
+            local_instance_index,
+        }
     }
 
-    // TODO: overload without indicator_count?
-    pub fn new<Fc>(constructor : Fc, indicators_count: usize) -> LeftRightRwLock<T>
+    /// Like [`new`](LeftRightRwLock::new), but lets the caller pick the
+    /// [`RelaxStrategy`] used while draining indicators, e.g.
+    /// `LeftRightRwLock::<_, SpinRelax>::with_backoff(...)`.
+    pub fn with_backoff<Fc>(constructor : Fc, indicators_count: usize) -> LeftRightRwLock<T, B>
         where Fc : Fn() -> T
     {
-        let mut result = LeftRightRwLock { 
-            instances           : UnsafeCell::new([constructor(), constructor()]),
+        let mut result = LeftRightRwLock {
+            instances           : [CachePadded(UnsafeCell::new(constructor())), CachePadded(UnsafeCell::new(constructor()))],
             instance_index      : AtomicUsize::new(0),
             indicators          : Vec::with_capacity(indicators_count),
             indicator_index     : AtomicUsize::new(0),
-            write_mutex         : Mutex::new(false)
+            write_mutex         : Mutex::new(false),
+            backoff             : PhantomData,
             };
 
         for _ in 0..indicators_count {
-            result.indicators.push([AtomicUsize::new(0), AtomicUsize::new(0)]);
+            result.indicators.push(CachePadded([AtomicUsize::new(0), AtomicUsize::new(0)]));
+        }
+
+        result
+    }
+
+    /// Like [`with_backoff`](LeftRightRwLock::with_backoff), but sizes the
+    /// shard count from [`std::thread::available_parallelism`] instead of
+    /// taking an explicit `indicators_count`.
+    pub fn new_auto_with_backoff<Fc>(constructor : Fc) -> LeftRightRwLock<T, B>
+        where Fc : Fn() -> T
+    {
+        let indicators_count = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+
+        Self::with_backoff(constructor, indicators_count)
+    }
+}
+
+/// RAII guard returned by [`read_guard`](LeftRightRwLock::read_guard):
+/// arrives at the indicator on construction, departs on `Drop`.
+pub struct ReadGuard<'a, T, B: RelaxStrategy> {
+    lock            : &'a LeftRightRwLock<T, B>,
+    indicator_index : usize,
+    reader_id       : usize,
+    instance_index  : usize,
+}
+
+impl<'a, T, B: RelaxStrategy> Deref for ReadGuard<'a, T, B> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        let index = self.instance_index;
+        self.lock.instances[index].with(|instance| unsafe { &*instance })
+    }
+}
+
+impl<'a, T, B: RelaxStrategy> Drop for ReadGuard<'a, T, B> {
+    fn drop(&mut self) {
+        self.lock.indicator_depart(self.reader_id, self.indicator_index);
+    }
+}
+
+/// RAII transaction guard returned by
+/// [`write_guard`](LeftRightRwLock::write_guard). Exposes the standby
+/// replica through `DerefMut`; on `Drop`, toggles the instances, drains
+/// the vacated indicator, then clones the now-current replica over the
+/// other one to keep both replicas in sync.
+pub struct WriteGuard<'a, T: Clone, B: RelaxStrategy> {
+    lock                  : &'a LeftRightRwLock<T, B>,
+    _mutex_guard          : MutexGuard<'a, bool>,
+    local_instance_index  : usize,
+}
+
+impl<'a, T: Clone, B: RelaxStrategy> Deref for WriteGuard<'a, T, B> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        let index = 1 - self.local_instance_index;
+        self.lock.instances[index].with(|instance| unsafe { &*instance })
+    }
+}
+
+impl<'a, T: Clone, B: RelaxStrategy> DerefMut for WriteGuard<'a, T, B> {
+    fn deref_mut(&mut self) -> &mut T {
+        let index = 1 - self.local_instance_index;
+        self.lock.instances[index].with_mut(|instance| unsafe { &mut *instance })
+    }
+}
+
+impl<'a, T: Clone, B: RelaxStrategy> Drop for WriteGuard<'a, T, B> {
+    fn drop(&mut self) {
+        let lock = self.lock;
+        let local_instance_index = self.local_instance_index;
+
+        lock.instance_index.store(1 - local_instance_index, Ordering::SeqCst);
+
+        // See write(): this fence pairs with the readers' SeqCst fence so
+        // the indicator scans below can't miss a reader of the old
+        // instance.
+        fence(Ordering::SeqCst);
+
+        let previous_indicator_index = lock.indicator_index.load(Ordering::SeqCst);
+        let next_indicator_index = 1 - previous_indicator_index;
+        let mut backoff = B::default();
+        while lock.indicator_is_set(next_indicator_index) {
+            backoff.relax();
         }
 
+        lock.indicator_index.store(next_indicator_index, Ordering::SeqCst);
+
+        let mut backoff = B::default();
+        while lock.indicator_is_set(previous_indicator_index) {
+            backoff.relax();
+        }
+
+        let updated = lock.instances[1 - local_instance_index]
+            .with(|instance| unsafe { (*instance).clone() });
+        lock.instances[local_instance_index]
+            .with_mut(|instance| unsafe { *instance = updated; });
+    }
+}
+
+impl<T> LeftRightRwLock<T, ExponentialBackoff> {
+    pub fn new<Fc>(constructor : Fc, indicators_count: usize) -> LeftRightRwLock<T, ExponentialBackoff>
+        where Fc : Fn() -> T
+    {
+        Self::with_backoff(constructor, indicators_count)
+    }
+
+    /// Like [`new`](LeftRightRwLock::new), but sizes the shard count from
+    /// [`std::thread::available_parallelism`] instead of taking an explicit
+    /// `indicators_count`.
+    pub fn new_auto<Fc>(constructor : Fc) -> LeftRightRwLock<T, ExponentialBackoff>
+        where Fc : Fn() -> T
+    {
+        Self::new_auto_with_backoff(constructor)
+    }
+
+    /// For `T: Copy` that's small enough to make keeping two full
+    /// replicas and an indicator array overkill (a `u64`, a pointer, a
+    /// tiny struct), returns a [`SeqLock`] instead: a single value guarded
+    /// by one sequence counter, as in the `seqlock` crate and crossbeam's
+    /// `AtomicCell`. Reads become optimistic/retrying rather than
+    /// strictly wait-free, which is the right tradeoff at this size.
+    pub fn new_seqlock(init: T) -> SeqLock<T>
+        where T : Copy
+    {
+        SeqLock::new(init)
+    }
+}
+
+/// Sequence-lock backend for small `Copy` values: a single `UnsafeCell<T>`
+/// guarded by one `AtomicUsize` sequence counter, rather than two
+/// replicas and an indicator array. Readers snapshot `seq` (retrying
+/// while it's odd, meaning a write is in flight), read the value, fence,
+/// then re-read `seq` and retry if it changed under them; writers take
+/// the write mutex, bump `seq` to odd, write, then bump it back to even.
+/// Reads are optimistic and may retry, unlike `LeftRightRwLock::read`
+/// which is wait-free.
+pub struct SeqLock<T> {
+    sequence    : AtomicUsize,
+    value       : UnsafeCell<T>,
+    write_mutex : Mutex<bool>,
+}
+
+unsafe impl<T> Sync for SeqLock<T> {}
+
+impl<T: Copy> SeqLock<T> {
+    pub fn new(init: T) -> SeqLock<T> {
+        SeqLock {
+            sequence    : AtomicUsize::new(0),
+            value       : UnsafeCell::new(init),
+            write_mutex : Mutex::new(false),
+        }
+    }
+
+    pub fn read<Fr, R>(&self, reader : Fr) -> R
+        where Fr : Fn(&T) -> R
+    {
+        loop {
+            let before = self.sequence.load(Ordering::Acquire);
+            if !before.is_multiple_of(2) {
+                yield_now();
+                continue;
+            }
+
+            // Copy the bytes out with a volatile read instead of forming a
+            // `&T` into `value`: a writer may concurrently hold `&mut T`
+            // into the same cell (see write() below), and an overlapping
+            // `&T`/`&mut T` pair is a data race on non-atomic memory even
+            // if the `seq` check below goes on to discard the result. The
+            // snapshot itself may still be torn; that's fine, it's only
+            // ever inspected after the sequence re-check confirms no
+            // write was in flight while it was taken.
+            let snapshot = self.value.with(|value| unsafe { std::ptr::read_volatile(value) });
+
+            fence(Ordering::Acquire);
+            let after = self.sequence.load(Ordering::Acquire);
+            if after == before {
+                return reader(&snapshot);
+            }
+        }
+    }
+
+    pub fn write<Fw, R>(&self, writer : Fw) -> R
+        where Fw : Fn(&mut T) -> R
+    {
+        let _guard = self.write_mutex.lock().unwrap();
+
+        let sequence = self.sequence.load(Ordering::Relaxed);
+        self.sequence.store(sequence.wrapping_add(1), Ordering::Release);
+        fence(Ordering::Release);
+
+        let result = self.value.with_mut(|value| unsafe { writer(&mut *value) });
+
+        self.sequence.store(sequence.wrapping_add(2), Ordering::Release);
+
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::LeftRightRwLock;
+    use std::sync::Arc;
+    use std::thread;
+
+    // Each writer writes a monotonically increasing "version" into every
+    // element of the replica; if a reader ever observed a torn update, at
+    // least one pair of elements would disagree. Intended to also be run
+    // under Miri (`cargo +nightly miri test`), which randomizes scheduling
+    // enough to catch ordering bugs that a plain stress run would miss.
+    #[test]
+    fn read_never_observes_a_half_updated_instance() {
+        const WIDTH: usize = 8;
+        let lock = Arc::new(LeftRightRwLock::new(|| [0usize; WIDTH], 4));
+
+        let writer_lock = lock.clone();
+        let writer = thread::spawn(move || {
+            for version in 1..200 {
+                writer_lock.write(move |data| {
+                    for slot in data.iter_mut() {
+                        *slot = version;
+                    }
+                });
+            }
+        });
+
+        let mut readers = Vec::new();
+        for _ in 0..4 {
+            let reader_lock = lock.clone();
+            readers.push(thread::spawn(move || {
+                for _ in 0..200 {
+                    reader_lock.read(|data| {
+                        let version = data[0];
+                        assert!(data.iter().all(|&slot| slot == version));
+                    });
+                }
+            }));
+        }
+
+        writer.join().unwrap();
+        for reader in readers {
+            reader.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn read_without_explicit_reader_id_still_sees_writes() {
+        let lock = LeftRightRwLock::new_auto(|| 0i32);
+        lock.write(|v| *v += 1);
+        assert_eq!(lock.read(|v| *v), 1);
+    }
+
+    #[test]
+    fn spin_relax_backoff_still_drains_correctly() {
+        use super::SpinRelax;
+
+        let lock = LeftRightRwLock::<_, SpinRelax>::with_backoff(|| 0i32, 4);
+        lock.write(|v| *v += 1);
+        assert_eq!(lock.read(|v| *v), 1);
+    }
+
+    #[test]
+    fn read_guard_derefs_to_the_current_value() {
+        let lock = LeftRightRwLock::new(|| vec![1, 2, 3], 4);
+        lock.write(|v| v.push(4));
+
+        let guard = lock.read_guard();
+        assert_eq!(*guard, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn write_guard_mutation_is_visible_to_later_reads() {
+        let lock = LeftRightRwLock::new(|| 0i32, 4);
+
+        {
+            let mut guard = lock.write_guard();
+            *guard += 1;
+            *guard += 1;
+        }
+
+        assert_eq!(lock.read(|v| *v), 2);
+        // A second write after the guard dropped should start from the
+        // replicated value, not from a stale standby replica.
+        lock.write(|v| *v += 1);
+        assert_eq!(lock.read(|v| *v), 3);
+    }
+
+    #[test]
+    fn seqlock_read_and_write_round_trip() {
+        let lock = LeftRightRwLock::new_seqlock(0u64);
+        lock.write(|v| *v += 41);
+        assert_eq!(lock.read(|v| *v), 41);
+    }
+
+    #[test]
+    fn seqlock_reader_never_observes_a_torn_write() {
+        use std::sync::Arc;
+        use std::thread;
+
+        const WIDTH: usize = 4;
+        let lock = Arc::new(LeftRightRwLock::new_seqlock([0u64; WIDTH]));
+
+        let writer_lock = lock.clone();
+        let writer = thread::spawn(move || {
+            for version in 1..500u64 {
+                writer_lock.write(move |data| {
+                    for slot in data.iter_mut() {
+                        *slot = version;
+                    }
+                });
+            }
+        });
+
+        let reader_lock = lock.clone();
+        let reader = thread::spawn(move || {
+            for _ in 0..500 {
+                reader_lock.read(|data| {
+                    let version = data[0];
+                    assert!(data.iter().all(|&slot| slot == version));
+                });
+            }
+        });
+
+        writer.join().unwrap();
+        reader.join().unwrap();
+    }
+}
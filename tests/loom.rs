@@ -0,0 +1,101 @@
+//! Exhaustive concurrency model checks for the instance/indicator toggling
+//! protocol, run under Loom's scheduler instead of relying on randomized
+//! stress tests. Loom's atomics, mutex and unsafe cell are swapped in via
+//! the crate's `sync` module when built with `--cfg loom`:
+//!
+//!     RUSTFLAGS="--cfg loom" cargo test --test loom --release
+//!
+//! These scenarios use `read_with_id` rather than `read` so they don't
+//! depend on `std::thread::LocalKey`, which Loom doesn't model.
+#![cfg(loom)]
+
+use left_right_rw_lock::LeftRightRwLock;
+use loom::sync::Arc;
+use loom::thread;
+
+#[test]
+fn one_writer_two_readers_never_see_a_torn_instance() {
+    loom::model(|| {
+        let lock = Arc::new(LeftRightRwLock::new(|| [0usize; 2], 2));
+
+        let writer = {
+            let lock = lock.clone();
+            thread::spawn(move || {
+                lock.write(|data| {
+                    data[0] += 1;
+                    data[1] += 1;
+                });
+            })
+        };
+
+        let reader_a = {
+            let lock = lock.clone();
+            thread::spawn(move || {
+                lock.read_with_id(0, |data| {
+                    assert_eq!(data[0], data[1]);
+                });
+            })
+        };
+
+        let reader_b = {
+            let lock = lock.clone();
+            thread::spawn(move || {
+                lock.read_with_id(1, |data| {
+                    assert_eq!(data[0], data[1]);
+                });
+            })
+        };
+
+        writer.join().unwrap();
+        reader_a.join().unwrap();
+        reader_b.join().unwrap();
+    });
+}
+
+#[test]
+fn two_writers_drain_and_terminate_under_a_concurrent_reader() {
+    loom::model(|| {
+        let lock = Arc::new(LeftRightRwLock::new(|| [0usize; 2], 2));
+
+        let writer_a = {
+            let lock = lock.clone();
+            thread::spawn(move || {
+                lock.write(|data| {
+                    data[0] += 1;
+                    data[1] += 1;
+                });
+            })
+        };
+
+        let writer_b = {
+            let lock = lock.clone();
+            thread::spawn(move || {
+                lock.write(|data| {
+                    data[0] += 1;
+                    data[1] += 1;
+                });
+            })
+        };
+
+        let reader = {
+            let lock = lock.clone();
+            thread::spawn(move || {
+                lock.read_with_id(0, |data| {
+                    assert_eq!(data[0], data[1]);
+                });
+            })
+        };
+
+        // Reaching these joins is itself part of what's being checked: a
+        // writer that never drains its indicator would hang here under
+        // every interleaving Loom explores.
+        writer_a.join().unwrap();
+        writer_b.join().unwrap();
+        reader.join().unwrap();
+
+        lock.read_with_id(0, |data| {
+            assert_eq!(data[0], 2);
+            assert_eq!(data[1], 2);
+        });
+    });
+}